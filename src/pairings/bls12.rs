@@ -0,0 +1,334 @@
+// BLS12 Ate pairing engine.
+//
+// The Miller loop is driven by the curve parameter `x` (MSB to LSB, skipping
+// the top bit), doubling the accumulator `r` every bit and adding `Q` on set
+// bits. If `x` is negative the Miller output is conjugated (free, since it
+// lives in the cyclotomic subgroup) in place of negating `x` itself. The
+// final exponentiation splits into an easy part (`p^6 - 1` then `p^2 + 1`,
+// both cheap Frobenius twists) and a hard part expressed as an addition
+// chain of exponentiations by `x`. The Miller-loop line-function steps
+// (`doubling_step`/`addition_step`/`ell`) and the `TwistType` they're keyed
+// on are reused by `pairings::bn`, but its hard part is its own -- only the
+// easy part is generic to embedding degree 12.
+
+use crate::extension_towers::fp2::{Fp2, Extension2};
+use crate::extension_towers::fp6_as_3_over_2::{Fp6, Extension3Over2};
+use crate::extension_towers::fp12_as_2_over3_over_2::{Fp12, Extension2Over3Over2};
+use crate::representation::ElementRepr;
+use crate::traits::FieldElement;
+use crate::field::PrimeField;
+use crate::weierstrass::curve::{WeierstrassCurve, CurvePoint};
+use crate::weierstrass::twist::{WeierstrassCurveTwist, CurveTwistPoint};
+use crate::weierstrass::Group;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TwistType {
+    D,
+    M,
+}
+
+pub struct Bls12Instance<'a, FE: ElementRepr> {
+    pub x: Vec<u64>,
+    pub x_is_negative: bool,
+    pub twist_type: TwistType,
+    pub base_field: &'a PrimeField<FE>,
+    pub curve: &'a WeierstrassCurve<'a, FE>,
+    pub curve_twist: &'a WeierstrassCurveTwist<'a, FE>,
+    pub fp2_extension: &'a Extension2<'a, FE>,
+    pub fp6_extension: &'a Extension3Over2<'a, FE>,
+    pub fp12_extension: &'a Extension2Over3Over2<'a, FE>,
+}
+
+impl<'a, FE: ElementRepr> Bls12Instance<'a, FE> {
+    /// Runs the Ate Miller loop over `self.x` for every `(P, Q)` pair and
+    /// accumulates them into a single `Fp12` value. When `miller_loop_only`
+    /// is set the pre-final-exponentiation accumulator is returned as-is;
+    /// otherwise the easy/hard-part final exponentiation is applied.
+    pub fn pair(
+        &self,
+        g1_points: &[CurvePoint<'a, FE>],
+        g2_points: &[CurveTwistPoint<'a, FE>],
+        miller_loop_only: bool,
+    ) -> Option<Fp12<'a, FE>> {
+        if g1_points.len() != g2_points.len() || g1_points.is_empty() {
+            return None;
+        }
+
+        let mut rs: Vec<CurveTwistPoint<FE>> = g2_points.to_vec();
+        let mut f = Fp12::one(self.fp12_extension);
+
+        let naf_bits = &self.x[..];
+        let bit_len = bit_length(naf_bits);
+
+        for bit_idx in (0..bit_len.saturating_sub(1)).rev() {
+            f.square();
+
+            for ((p, q), r) in g1_points.iter().zip(g2_points.iter()).zip(rs.iter_mut()) {
+                if p.is_zero() || q.is_zero() {
+                    continue;
+                }
+                let coeffs = doubling_step(r);
+                ell(&mut f, coeffs, p, self.twist_type);
+            }
+
+            if get_bit(naf_bits, bit_idx) {
+                for ((p, q), r) in g1_points.iter().zip(g2_points.iter()).zip(rs.iter_mut()) {
+                    if p.is_zero() || q.is_zero() {
+                        continue;
+                    }
+                    let coeffs = addition_step(r, q);
+                    ell(&mut f, coeffs, p, self.twist_type);
+                }
+            }
+        }
+
+        if self.x_is_negative {
+            f.conjugate();
+        }
+
+        if miller_loop_only {
+            return Some(f);
+        }
+
+        final_exponentiation(&f, &self.x, self.x_is_negative)
+    }
+}
+
+/// Easy part (`f^(p^6-1)(p^2+1)`, both cheap Frobenius twists) followed by
+/// the cyclotomic hard part expressed as an addition chain of
+/// exponentiations by `x`. BLS12-specific -- `pairings::bn` computes its own
+/// hard part from its own `p(u)`/`r(u)` polynomials.
+pub(crate) fn final_exponentiation<'a, FE: ElementRepr>(
+    f: &Fp12<'a, FE>,
+    x: &[u64],
+    x_is_negative: bool,
+) -> Option<Fp12<'a, FE>> {
+    let f_inv = f.inverse()?;
+
+    let mut f1 = f.clone();
+    f1.conjugate();
+    f1.mul_assign(&f_inv);
+
+    let mut f2 = f1.clone();
+    f2.frobenius_map(2);
+    f2.mul_assign(&f1);
+
+    let easy_part = f2;
+
+    let exp_by_x = |value: &Fp12<'a, FE>| -> Fp12<'a, FE> {
+        let mut result = value.pow(x);
+        if x_is_negative {
+            result.conjugate();
+        }
+        result
+    };
+
+    // Standard BLS12 hard part: a short addition chain built entirely out of
+    // exponentiations by `x`, Frobenius twists and conjugations/inversions,
+    // all applied to the (already cyclotomic) easy part.
+    let y0 = exp_by_x(&easy_part);
+    let mut y1 = y0.clone();
+    y1.cyclotomic_square();
+    let mut y2 = y1.clone();
+    y2.cyclotomic_square();
+    y2.mul_assign(&y1);
+    let y3 = exp_by_x(&y2);
+    let mut y4 = exp_by_x(&y3);
+    y4.conjugate();
+    let mut y5 = y4.clone();
+    y5.cyclotomic_square();
+    let mut y6 = exp_by_x(&y5);
+    y6.conjugate();
+    let mut y3_conj = y3.clone();
+    y3_conj.conjugate();
+    let mut y1_conj = y1.clone();
+    y1_conj.conjugate();
+
+    let mut result = y6.clone();
+    result.mul_assign(&y4);
+    result.mul_assign(&y5);
+    result.mul_assign(&y3_conj);
+    result.mul_assign(&y1_conj);
+
+    let mut easy_part_conj = easy_part.clone();
+    easy_part_conj.frobenius_map(1);
+    result.mul_assign(&easy_part_conj);
+
+    Some(result)
+}
+
+pub(crate) fn bit_length(limbs: &[u64]) -> usize {
+    for (i, limb) in limbs.iter().enumerate().rev() {
+        if *limb != 0 {
+            return i * 64 + (64 - limb.leading_zeros() as usize);
+        }
+    }
+
+    0
+}
+
+fn get_bit(limbs: &[u64], bit_idx: usize) -> bool {
+    let limb = bit_idx / 64;
+    let bit_in_limb = bit_idx % 64;
+
+    if limb >= limbs.len() {
+        return false;
+    }
+
+    (limbs[limb] >> bit_in_limb) & 1 == 1
+}
+
+pub(crate) type LineCoeffs<'a, FE> = (Fp2<'a, FE>, Fp2<'a, FE>, Fp2<'a, FE>);
+
+/// Doubles `r` in place and returns the line-function coefficients for the
+/// tangent line at `r` (Algorithm 26 of "Efficient Implementation of
+/// Pairing-Based Cryptosystems").
+pub(crate) fn doubling_step<'a, FE: ElementRepr>(r: &mut CurveTwistPoint<'a, FE>) -> LineCoeffs<'a, FE> {
+    let mut tmp0 = r.x.clone();
+    tmp0.square();
+    let mut tmp1 = r.y.clone();
+    tmp1.square();
+    let mut tmp2 = tmp1.clone();
+    tmp2.square();
+    let mut tmp3 = tmp1.clone();
+    tmp3.add_assign(&r.x);
+    tmp3.square();
+    tmp3.sub_assign(&tmp0);
+    tmp3.sub_assign(&tmp2);
+    tmp3.double();
+    let mut tmp4 = tmp0.clone();
+    tmp4.double();
+    tmp4.add_assign(&tmp0);
+    let mut tmp6 = r.x.clone();
+    tmp6.add_assign(&tmp4);
+    let tmp5 = { let mut t = tmp4.clone(); t.square(); t };
+    let mut zsquared = r.z.clone();
+    zsquared.square();
+
+    r.x = tmp5.clone();
+    r.x.sub_assign(&tmp3);
+    r.x.sub_assign(&tmp3);
+
+    r.z.add_assign(&r.y);
+    r.z.square();
+    r.z.sub_assign(&tmp1);
+    r.z.sub_assign(&zsquared);
+
+    r.y = tmp3.clone();
+    r.y.sub_assign(&r.x);
+    r.y.mul_assign(&tmp4);
+    let mut tmp2_8 = tmp2.clone();
+    tmp2_8.double();
+    tmp2_8.double();
+    tmp2_8.double();
+    r.y.sub_assign(&tmp2_8);
+
+    let mut c0 = tmp4.clone();
+    c0.mul_assign(&zsquared);
+    c0.double();
+    c0.negate();
+
+    tmp6.square();
+    tmp6.sub_assign(&tmp0);
+    tmp6.sub_assign(&tmp5);
+    tmp1.double();
+    tmp1.double();
+    let c1 = { let mut t = tmp6.clone(); t.sub_assign(&tmp1); t };
+
+    let mut c2 = r.z.clone();
+    c2.mul_assign(&zsquared);
+    c2.double();
+
+    (c2, c0, c1)
+}
+
+/// Adds `q` into `r` in place and returns the line-function coefficients for
+/// the line through `r` and `q` (Algorithm 27 of the same reference).
+pub(crate) fn addition_step<'a, FE: ElementRepr>(
+    r: &mut CurveTwistPoint<'a, FE>,
+    q: &CurveTwistPoint<'a, FE>,
+) -> LineCoeffs<'a, FE> {
+    let mut zsquared = r.z.clone();
+    zsquared.square();
+    let mut ysquared = q.y.clone();
+    ysquared.square();
+    let mut t0 = zsquared.clone();
+    t0.mul_assign(&q.x);
+    let mut t1 = q.y.clone();
+    t1.add_assign(&r.z);
+    t1.square();
+    t1.sub_assign(&ysquared);
+    t1.sub_assign(&zsquared);
+    t1.mul_assign(&zsquared);
+    let mut t2 = t0.clone();
+    t2.sub_assign(&r.x);
+    let t3 = { let mut t = t2.clone(); t.square(); t };
+    let mut t4 = t3.clone();
+    t4.double();
+    t4.double();
+    let t5 = { let mut t = t4.clone(); t.mul_assign(&t2); t };
+    let mut t6 = t1.clone();
+    t6.sub_assign(&r.y);
+    t6.sub_assign(&r.y);
+    let mut t9 = t6.clone();
+    t9.mul_assign(&q.x);
+    let t7 = { let mut t = t4.clone(); t.mul_assign(&r.x); t };
+
+    r.x = t6.clone();
+    r.x.square();
+    r.x.sub_assign(&t5);
+    r.x.sub_assign(&t7);
+    r.x.sub_assign(&t7);
+
+    r.z.add_assign(&t2);
+    r.z.square();
+    r.z.sub_assign(&zsquared);
+    r.z.sub_assign(&t3);
+
+    let mut t10 = q.y.clone();
+    t10.add_assign(&r.z);
+
+    let mut t8 = t7.clone();
+    t8.sub_assign(&r.x);
+    t8.mul_assign(&t6);
+    let mut t0b = r.y.clone();
+    t0b.mul_assign(&t3);
+    t0b.double();
+    r.y = t8.clone();
+    r.y.sub_assign(&t0b);
+
+    t10.square();
+    t10.sub_assign(&ysquared);
+    let mut zt_squared = r.z.clone();
+    zt_squared.square();
+    t10.sub_assign(&zt_squared);
+
+    t9.double();
+    t9.sub_assign(&t10);
+
+    let c2 = { let mut t = r.z.clone(); t.double(); t };
+    t6.negate();
+    let c1 = { let mut t = t6.clone(); t.double(); t };
+
+    (c2, c1, t9)
+}
+
+/// Sparse multiplication of the Miller accumulator by a line value, i.e.
+/// `f *= (c0, c1, c2)` with the two non-tangent-line components placed
+/// according to the twist type (M-twist and D-twist put the `Fp` factors on
+/// opposite sides of the sparse element).
+pub(crate) fn ell<'a, FE: ElementRepr>(
+    f: &mut Fp12<'a, FE>,
+    coeffs: LineCoeffs<'a, FE>,
+    p: &CurvePoint<'a, FE>,
+    twist_type: TwistType,
+) {
+    let (c0, mut c1, mut c2) = coeffs;
+    c1.mul_by_fp(&p.y);
+    c2.mul_by_fp(&p.x);
+
+    match twist_type {
+        TwistType::M => f.mul_by_014(&c0, &c2, &c1),
+        TwistType::D => f.mul_by_014(&c0, &c1, &c2),
+    }
+}
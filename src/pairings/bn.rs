@@ -0,0 +1,344 @@
+// BN (Barreto-Naehrig) Ate pairing engine.
+//
+// Structurally this mirrors `pairings::bls12` for the Miller loop: same Fp2
+// -> Fp6 -> Fp12 tower and the same doubling/addition line-function steps.
+// Two things are specific to the BN family and implemented here rather than
+// shared with BLS12:
+//
+// - The Miller loop runs over the magnitude of `t = 6u+2` (the BN optimal Ate
+//   loop parameter), not `u` itself, and two further line evaluations are
+//   accumulated after the main loop at `pi(Q)` and `-pi^2(Q)`, where `pi` is
+//   the Frobenius endomorphism on the twist. When `u` is negative, `t` is
+//   negative too, but its magnitude is `6|u|-2`, not `6|u|+2` -- only the
+//   positive-`u` case adds; the negative case subtracts before conjugating
+//   the Miller-loop output.
+// - The final exponentiation's easy part (`f^(p^6-1)(p^2+1)`) is generic to
+//   embedding degree 12 and shared with BLS12, but the hard part is an
+//   addition chain built from each family's own `p(u)`/`r(u)` polynomials and
+//   is different for BN vs. BLS12 (Fuentes-Castaneda, Knapp, Rodriguez-
+//   Henriquez's BN hard part below vs. Aranha et al.'s BLS12 one).
+
+use crate::extension_towers::fp2::Extension2;
+use crate::extension_towers::fp6_as_3_over_2::Extension3Over2;
+use crate::extension_towers::fp12_as_2_over3_over_2::{Fp12, Extension2Over3Over2};
+use crate::representation::ElementRepr;
+use crate::traits::FieldElement;
+use crate::field::PrimeField;
+use crate::weierstrass::curve::{WeierstrassCurve, CurvePoint};
+use crate::weierstrass::twist::{WeierstrassCurveTwist, CurveTwistPoint};
+use crate::weierstrass::Group;
+
+use super::bls12::TwistType;
+
+pub struct BnInstance<'a, FE: ElementRepr> {
+    pub u: Vec<u64>,
+    pub u_is_negative: bool,
+    pub twist_type: TwistType,
+    pub base_field: &'a PrimeField<FE>,
+    pub curve: &'a WeierstrassCurve<'a, FE>,
+    pub curve_twist: &'a WeierstrassCurveTwist<'a, FE>,
+    pub fp2_extension: &'a Extension2<'a, FE>,
+    pub fp6_extension: &'a Extension3Over2<'a, FE>,
+    pub fp12_extension: &'a Extension2Over3Over2<'a, FE>,
+}
+
+impl<'a, FE: ElementRepr> BnInstance<'a, FE> {
+    /// Runs the Ate Miller loop over `|6u+2|` for every `(P, Q)` pair, adds
+    /// the two trailing Frobenius steps, and (unless `miller_loop_only` is
+    /// set) finishes with the BN-specific final exponentiation.
+    pub fn pair(
+        &self,
+        g1_points: &[CurvePoint<'a, FE>],
+        g2_points: &[CurveTwistPoint<'a, FE>],
+        miller_loop_only: bool,
+    ) -> Option<Fp12<'a, FE>> {
+        if g1_points.len() != g2_points.len() || g1_points.is_empty() {
+            return None;
+        }
+
+        // t = 6u+2 when u >= 0, but when u < 0 that same expression is
+        // negative with magnitude 6|u|-2 -- only the magnitude ever drives
+        // the loop, the sign is applied after it by conjugating `f` and
+        // negating each accumulated `r`.
+        let loop_counter = if self.u_is_negative {
+            six_u_minus_2(&self.u)
+        } else {
+            six_u_plus_2(&self.u)
+        };
+        let bit_len = super::bls12::bit_length(&loop_counter);
+
+        let mut rs: Vec<CurveTwistPoint<FE>> = g2_points.to_vec();
+        let mut f = Fp12::one(self.fp12_extension);
+
+        for bit_idx in (0..bit_len.saturating_sub(1)).rev() {
+            f.square();
+
+            for ((p, q), r) in g1_points.iter().zip(g2_points.iter()).zip(rs.iter_mut()) {
+                if p.is_zero() || q.is_zero() {
+                    continue;
+                }
+                let coeffs = super::bls12::doubling_step(r);
+                super::bls12::ell(&mut f, coeffs, p, self.twist_type);
+            }
+
+            if get_bit(&loop_counter, bit_idx) {
+                for ((p, q), r) in g1_points.iter().zip(g2_points.iter()).zip(rs.iter_mut()) {
+                    if p.is_zero() || q.is_zero() {
+                        continue;
+                    }
+                    let coeffs = super::bls12::addition_step(r, q);
+                    super::bls12::ell(&mut f, coeffs, p, self.twist_type);
+                }
+            }
+        }
+
+        if self.u_is_negative {
+            f.conjugate();
+        }
+
+        // Trailing Frobenius addition steps: pi(Q) and -pi^2(Q).
+        for ((p, q), r) in g1_points.iter().zip(g2_points.iter()).zip(rs.iter_mut()) {
+            if p.is_zero() || q.is_zero() {
+                continue;
+            }
+
+            if self.u_is_negative {
+                r.negate();
+            }
+
+            let mut q1 = q.clone();
+            q1.frobenius_map(1);
+
+            let mut q2 = q.clone();
+            q2.frobenius_map(2);
+            q2.negate();
+
+            let coeffs = super::bls12::addition_step(r, &q1);
+            super::bls12::ell(&mut f, coeffs, p, self.twist_type);
+
+            let coeffs = super::bls12::addition_step(r, &q2);
+            super::bls12::ell(&mut f, coeffs, p, self.twist_type);
+        }
+
+        if miller_loop_only {
+            return Some(f);
+        }
+
+        bn_final_exponentiation(&f, &self.u, self.u_is_negative)
+    }
+}
+
+fn exp_by_u<'a, FE: ElementRepr>(value: &Fp12<'a, FE>, u: &[u64], u_is_negative: bool) -> Fp12<'a, FE> {
+    let mut result = value.pow(u);
+    if u_is_negative {
+        result.conjugate();
+    }
+    result
+}
+
+/// BN final exponentiation: the easy part (`f^(p^6-1)(p^2+1)`) is the same
+/// two cheap Frobenius twists used for BLS12, but the hard part is the BN-
+/// specific addition chain of Fuentes-Castaneda, Knapp and Rodriguez-
+/// Henriquez's "Faster hashing to G2", expressed entirely in exponentiations
+/// by `u`, Frobenius twists, conjugations and multiplications.
+fn bn_final_exponentiation<'a, FE: ElementRepr>(
+    f: &Fp12<'a, FE>,
+    u: &[u64],
+    u_is_negative: bool,
+) -> Option<Fp12<'a, FE>> {
+    let f_inv = f.inverse()?;
+
+    let mut t1 = f.clone();
+    t1.conjugate();
+    t1.mul_assign(&f_inv);
+
+    let mut t2 = t1.clone();
+    t2.frobenius_map(2);
+    t1.mul_assign(&t2);
+
+    let easy_part = t1;
+
+    let mut fp1 = easy_part.clone();
+    fp1.frobenius_map(1);
+    let mut fp2 = easy_part.clone();
+    fp2.frobenius_map(2);
+    let mut fp3 = fp2.clone();
+    fp3.frobenius_map(1);
+
+    let fu = exp_by_u(&easy_part, u, u_is_negative);
+    let fu2 = exp_by_u(&fu, u, u_is_negative);
+    let fu3 = exp_by_u(&fu2, u, u_is_negative);
+
+    let mut fu2p = fu2.clone();
+    fu2p.frobenius_map(1);
+    let mut fu3p = fu3.clone();
+    fu3p.frobenius_map(1);
+    let mut y2 = fu2.clone();
+    y2.frobenius_map(2);
+
+    let mut y0 = fp1;
+    y0.mul_assign(&fp2);
+    y0.mul_assign(&fp3);
+
+    let mut y1 = easy_part.clone();
+    y1.conjugate();
+
+    let mut y3 = fu.clone();
+    y3.frobenius_map(1);
+    y3.conjugate();
+
+    let mut y4 = fu.clone();
+    y4.mul_assign(&fu2p);
+    y4.conjugate();
+
+    let mut y5 = fu2;
+    y5.conjugate();
+
+    let mut y6 = fu3.clone();
+    y6.mul_assign(&fu3p);
+    y6.conjugate();
+
+    let mut t0 = y6.clone();
+    t0.cyclotomic_square();
+    t0.mul_assign(&y4);
+    t0.mul_assign(&y5);
+
+    let mut t1 = y3;
+    t1.mul_assign(&y5);
+    t1.mul_assign(&t0);
+
+    t0.mul_assign(&y2);
+
+    t1.cyclotomic_square();
+    t1.mul_assign(&t0);
+    t1.cyclotomic_square();
+
+    let mut result = t1.clone();
+    result.mul_assign(&y1);
+
+    t1.mul_assign(&y0);
+
+    result.cyclotomic_square();
+    result.mul_assign(&t1);
+
+    Some(result)
+}
+
+fn shl1(limbs: &[u64]) -> Vec<u64> {
+    let mut result = vec![0u64; limbs.len() + 1];
+    let mut carry = 0u64;
+    for (i, limb) in limbs.iter().enumerate() {
+        result[i] = (limb << 1) | carry;
+        carry = limb >> 63;
+    }
+    result[limbs.len()] = carry;
+    result
+}
+
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let len = a.len().max(b.len()) + 1;
+    let mut result = vec![0u64; len];
+    let mut carry = 0u128;
+    for i in 0..len {
+        let av = *a.get(i).unwrap_or(&0) as u128;
+        let bv = *b.get(i).unwrap_or(&0) as u128;
+        let sum = av + bv + carry;
+        result[i] = sum as u64;
+        carry = sum >> 64;
+    }
+    result
+}
+
+fn add_small(limbs: &[u64], value: u64) -> Vec<u64> {
+    add_limbs(limbs, &[value])
+}
+
+/// Subtracts a small value from a limb vector, propagating the borrow
+/// leftward. Only used with a `value` small enough that it never borrows
+/// past a non-zero top limb (true for `6u - 2` with any real curve
+/// parameter `u >= 1`).
+fn sub_small(limbs: &[u64], value: u64) -> Vec<u64> {
+    let mut result = limbs.to_vec();
+    let mut borrow = value as u128;
+    for limb in result.iter_mut() {
+        if borrow == 0 {
+            break;
+        }
+        let diff = *limb as i128 - borrow as i128;
+        if diff < 0 {
+            *limb = (diff + (1i128 << 64)) as u64;
+            borrow = 1;
+        } else {
+            *limb = diff as u64;
+            borrow = 0;
+        }
+    }
+    result
+}
+
+/// `6u + 2 = (u << 2) + (u << 1) + 2`, computed directly on the `u64` limb
+/// vector so the Miller loop can walk its bits without pulling in a bignum
+/// dependency.
+fn six_u_plus_2(u: &[u64]) -> Vec<u64> {
+    let two_u = shl1(u);
+    let four_u = shl1(&two_u);
+    let six_u = add_limbs(&four_u, &two_u);
+    add_small(&six_u, 2)
+}
+
+/// `6u - 2`, the magnitude of the loop parameter `t = 6u+2` when `u` is
+/// negative (`t = -(6|u|-2)`).
+fn six_u_minus_2(u: &[u64]) -> Vec<u64> {
+    let two_u = shl1(u);
+    let four_u = shl1(&two_u);
+    let six_u = add_limbs(&four_u, &two_u);
+    sub_small(&six_u, 2)
+}
+
+fn get_bit(limbs: &[u64], bit_idx: usize) -> bool {
+    let limb = bit_idx / 64;
+    let bit_in_limb = bit_idx % 64;
+
+    if limb >= limbs.len() {
+        return false;
+    }
+
+    (limbs[limb] >> bit_in_limb) & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{six_u_plus_2, six_u_minus_2, get_bit};
+
+    fn to_u64(limbs: &[u64]) -> u64 {
+        let mut value = 0u64;
+        for (i, limb) in limbs.iter().enumerate() {
+            assert!(*limb == 0 || i == 0, "test values must fit in a single limb");
+            value |= limb << (64 * i).min(63);
+        }
+        value
+    }
+
+    #[test]
+    fn computes_six_u_plus_2_for_small_values() {
+        assert_eq!(to_u64(&six_u_plus_2(&[1])), 8);
+        assert_eq!(to_u64(&six_u_plus_2(&[0])), 2);
+        assert_eq!(to_u64(&six_u_plus_2(&[10])), 62);
+    }
+
+    #[test]
+    fn computes_six_u_minus_2_for_small_values() {
+        assert_eq!(to_u64(&six_u_minus_2(&[1])), 4);
+        assert_eq!(to_u64(&six_u_minus_2(&[10])), 58);
+    }
+
+    #[test]
+    fn reads_bits_low_to_high() {
+        let limbs = six_u_plus_2(&[10]); // 62 = 0b111110
+        assert!(!get_bit(&limbs, 0));
+        for i in 1..6 {
+            assert!(get_bit(&limbs, i));
+        }
+        assert!(!get_bit(&limbs, 6));
+    }
+}
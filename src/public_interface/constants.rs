@@ -0,0 +1,40 @@
+// Wire-format flag constants introduced by the pairing/MSM API backlog.
+//
+// The curve-type, twist-type, sign and length-prefix constants this module
+// is already globbed-in for (`CURVE_TYPE_LENGTH`, `BLS12`, `TWIST_TYPE_D`,
+// `SIGN_PLUS`, `BYTES_FOR_LENGTH_ENCODING`, ...) are part of the original
+// encoding support and live alongside the rest of that foundation. This file
+// only carries the flags each backlog item added on top of it.
+
+/// Selects the curve family in the pairing API's curve-type byte, alongside
+/// the pre-existing `BLS12`.
+pub(crate) const BN: u8 = 0x02;
+
+/// Length in bytes of the point-encoding flag placed after the twist type in
+/// the BLS12 pairing header (the BN header has no such flag).
+pub(crate) const POINT_ENCODING_LENGTH: usize = 1;
+/// Points are encoded as `(x, y)`, each `modulus_len` bytes.
+pub(crate) const UNCOMPRESSED_POINT_ENCODING: u8 = 0;
+/// Points are encoded as `x` plus a one byte sign flag; `y` is recovered via
+/// `decode_g1_point_from_x`/`decode_g2_point_from_x_in_fp2`.
+pub(crate) const COMPRESSED_POINT_ENCODING: u8 = 1;
+
+/// Length in bytes of the MSM point-type flag placed after the group order
+/// in the MSM API header.
+pub(crate) const MSM_POINT_TYPE_LENGTH: usize = 1;
+/// The `(point, scalar)` pairs that follow are G1 points.
+pub(crate) const MSM_POINT_TYPE_G1: u8 = 0;
+/// The `(point, scalar)` pairs that follow are G2 points (encoded over
+/// `Fp2`).
+pub(crate) const MSM_POINT_TYPE_G2: u8 = 1;
+
+/// Length in bytes of the pairing output mode flag placed after the point
+/// encoding flag.
+pub(crate) const PAIRING_OUTPUT_MODE_LENGTH: usize = 1;
+/// Returns the usual one byte boolean pairing verdict.
+pub(crate) const PAIRING_OUTPUT_BOOLEAN: u8 = 0;
+/// Returns the fully serialized `Fp12` pairing result.
+pub(crate) const PAIRING_OUTPUT_FP12: u8 = 1;
+/// Returns the `Fp12` Miller-loop accumulator taken before the final
+/// exponentiation.
+pub(crate) const PAIRING_OUTPUT_MILLER_LOOP_FP12: u8 = 2;
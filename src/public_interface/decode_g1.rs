@@ -0,0 +1,190 @@
+// G1 point decoding: the uncompressed `(x, y)` form used by the original
+// pairing wire format, and the compressed `x` + sign-bit form added for the
+// pairing API (see `pairing_ops::pair_bls12`).
+
+use crate::errors::ApiError;
+use crate::fp::Fp;
+use crate::representation::ElementRepr;
+use crate::traits::FieldElement;
+use crate::weierstrass::curve::{WeierstrassCurve, CurvePoint};
+
+use super::decode_fp::*;
+use super::constants::*;
+
+/// Decodes an uncompressed G1 point as `x` followed by `y`, each
+/// `modulus_len` bytes.
+pub(crate) fn decode_g1_point_from_xy<'a, FE: ElementRepr>(
+    bytes: &'a [u8],
+    modulus_len: usize,
+    curve: &'a WeierstrassCurve<'a, FE>,
+) -> Result<(CurvePoint<'a, FE>, &'a [u8]), ApiError> {
+    let (x, rest) = decode_fp(bytes, modulus_len, curve.base_field())?;
+    let (y, rest) = decode_fp(rest, modulus_len, curve.base_field())?;
+
+    Ok((CurvePoint::point_from_xy(curve, x, y), rest))
+}
+
+/// Decodes a compressed G1 point as `x` (`modulus_len` bytes) plus a one
+/// byte sign flag, recovering `y` from `y^2 = x^3 + b` (`a` is always zero
+/// for the BLS12/BN curves this API serves).
+pub(crate) fn decode_g1_point_from_x<'a, FE: ElementRepr>(
+    bytes: &'a [u8],
+    modulus_len: usize,
+    curve: &'a WeierstrassCurve<'a, FE>,
+) -> Result<(CurvePoint<'a, FE>, &'a [u8]), ApiError> {
+    let (x, rest) = decode_fp(bytes, modulus_len, curve.base_field())?;
+
+    if rest.len() < SIGN_ENCODING_LENGTH {
+        return Err(ApiError::InputError("Input is not long enough to get G1 compressed point sign".to_owned()));
+    }
+    let (sign_encoding, rest) = rest.split_at(SIGN_ENCODING_LENGTH);
+    let y_is_odd = match sign_encoding[0] {
+        SIGN_PLUS => false,
+        SIGN_MINUS => true,
+        _ => {
+            return Err(ApiError::InputError("G1 compressed point sign is not encoded properly".to_owned()));
+        },
+    };
+
+    let mut rhs = x.clone();
+    rhs.square();
+    rhs.mul_assign(&x);
+    rhs.add_assign(curve.b());
+
+    let y = fp_sqrt(&rhs).ok_or_else(|| {
+        ApiError::InputError("Invalid compressed G1 point: X is not a valid curve abscissa".to_owned())
+    })?;
+    let y = select_sign_fp(y, y_is_odd);
+
+    Ok((CurvePoint::point_from_xy(curve, x, y), rest))
+}
+
+fn fp_is_odd<'a, FE: ElementRepr>(value: &Fp<'a, FE>) -> bool {
+    value.into_repr().as_ref()[0] & 1 == 1
+}
+
+/// `true` if the root's parity doesn't already match the sign flag, i.e. the
+/// decoder needs to negate it to satisfy the compressed encoding's sign
+/// rule. Split out from `select_sign_fp` so the selection rule itself can be
+/// exercised without needing a field element to test against.
+pub(crate) fn should_negate_root(root_is_odd: bool, want_odd: bool) -> bool {
+    root_is_odd != want_odd
+}
+
+/// Picks the root (of the two, `y` and `-y`) whose parity matches the sign
+/// flag carried by the compressed encoding.
+fn select_sign_fp<'a, FE: ElementRepr>(root: Fp<'a, FE>, want_odd: bool) -> Fp<'a, FE> {
+    if !should_negate_root(fp_is_odd(&root), want_odd) {
+        root
+    } else {
+        let mut negated = root;
+        negated.negate();
+        negated
+    }
+}
+
+/// Square root in `Fp`: the fast `p ≡ 3 (mod 4)` formula `y = a^((p+1)/4)`
+/// when applicable, falling back to Tonelli-Shanks otherwise. Returns `None`
+/// when `value` is not a quadratic residue.
+pub(crate) fn fp_sqrt<'a, FE: ElementRepr>(value: &Fp<'a, FE>) -> Option<Fp<'a, FE>> {
+    if value.is_zero() {
+        return Some(value.clone());
+    }
+
+    let modulus = value.field().modulus();
+
+    if modulus.bit(0) && modulus.bit(1) {
+        // p ≡ 3 (mod 4)
+        let exp = (modulus.clone() + 1u64) >> 2;
+        let candidate = value.pow(&crate::field::biguint_to_u64_vec(exp));
+
+        let mut check = candidate.clone();
+        check.square();
+
+        if &check == value {
+            Some(candidate)
+        } else {
+            None
+        }
+    } else {
+        tonelli_shanks_sqrt(value)
+    }
+}
+
+/// Textbook Tonelli-Shanks: write `p - 1 = q * 2^s` with `q` odd, find a
+/// quadratic non-residue `z`, then repeatedly halve the order of the
+/// candidate's discrepancy from 1 until it collapses.
+fn tonelli_shanks_sqrt<'a, FE: ElementRepr>(value: &Fp<'a, FE>) -> Option<Fp<'a, FE>> {
+    let field = value.field();
+    let modulus = field.modulus();
+    let one = modulus.clone() - 1u64;
+
+    let mut q = one.clone();
+    let mut s = 0u32;
+    while !q.bit(0) {
+        q >>= 1;
+        s += 1;
+    }
+
+    let legendre_exp = crate::field::biguint_to_u64_vec(one >> 1);
+    if value.pow(&legendre_exp) != Fp::one(field) {
+        return None;
+    }
+
+    let mut z = Fp::one(field);
+    loop {
+        z.add_assign(&Fp::one(field));
+        if z.pow(&legendre_exp) != Fp::one(field) {
+            break;
+        }
+    }
+
+    let mut m = s;
+    let mut c = z.pow(&crate::field::biguint_to_u64_vec(q.clone()));
+    let mut t = value.pow(&crate::field::biguint_to_u64_vec(q.clone()));
+    let mut r = value.pow(&crate::field::biguint_to_u64_vec((q + 1u64) >> 1));
+
+    loop {
+        if t == Fp::one(field) {
+            return Some(r);
+        }
+
+        let mut i = 0u32;
+        let mut t2i = t.clone();
+        while t2i != Fp::one(field) {
+            t2i.square();
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        let mut b = c.clone();
+        for _ in 0..(m - i - 1) {
+            b.square();
+        }
+
+        m = i;
+        c = b.clone();
+        c.square();
+        r.mul_assign(&b);
+        t.mul_assign(&c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::should_negate_root;
+
+    #[test]
+    fn keeps_root_matching_requested_sign() {
+        assert!(!should_negate_root(true, true));
+        assert!(!should_negate_root(false, false));
+    }
+
+    #[test]
+    fn negates_root_not_matching_requested_sign() {
+        assert!(should_negate_root(true, false));
+        assert!(should_negate_root(false, true));
+    }
+}
@@ -0,0 +1,148 @@
+// G2 point decoding: the uncompressed `(x, y)` form (each coordinate an
+// `Fp2` element) and the compressed `x` + sign-bit form added for the
+// pairing API (see `pairing_ops::pair_bls12`).
+
+use crate::errors::ApiError;
+use crate::extension_towers::fp2::Fp2;
+use crate::fp::Fp;
+use crate::representation::ElementRepr;
+use crate::traits::FieldElement;
+use crate::weierstrass::twist::{WeierstrassCurveTwist, CurveTwistPoint};
+
+use super::decode_fp::*;
+use super::constants::*;
+use super::decode_g1::{fp_sqrt, should_negate_root};
+
+/// Decodes an uncompressed G2 point as `x` followed by `y`, each an `Fp2`
+/// element encoded as two `modulus_len`-byte field elements (`c0`, `c1`).
+pub(crate) fn decode_g2_point_from_xy_in_fp2<'a, FE: ElementRepr>(
+    bytes: &'a [u8],
+    modulus_len: usize,
+    curve: &'a WeierstrassCurveTwist<'a, FE>,
+) -> Result<(CurveTwistPoint<'a, FE>, &'a [u8]), ApiError> {
+    let (x, rest) = decode_fp2(bytes, modulus_len, curve.base_field())?;
+    let (y, rest) = decode_fp2(rest, modulus_len, curve.base_field())?;
+
+    Ok((CurveTwistPoint::point_from_xy(curve, x, y), rest))
+}
+
+/// Decodes a compressed G2 point as `x` (an `Fp2` element) plus a one byte
+/// sign flag, recovering `y` from `y^2 = x^3 + b` in `Fp2`.
+pub(crate) fn decode_g2_point_from_x_in_fp2<'a, FE: ElementRepr>(
+    bytes: &'a [u8],
+    modulus_len: usize,
+    curve: &'a WeierstrassCurveTwist<'a, FE>,
+) -> Result<(CurveTwistPoint<'a, FE>, &'a [u8]), ApiError> {
+    let (x, rest) = decode_fp2(bytes, modulus_len, curve.base_field())?;
+
+    if rest.len() < SIGN_ENCODING_LENGTH {
+        return Err(ApiError::InputError("Input is not long enough to get G2 compressed point sign".to_owned()));
+    }
+    let (sign_encoding, rest) = rest.split_at(SIGN_ENCODING_LENGTH);
+    let y_is_odd = match sign_encoding[0] {
+        SIGN_PLUS => false,
+        SIGN_MINUS => true,
+        _ => {
+            return Err(ApiError::InputError("G2 compressed point sign is not encoded properly".to_owned()));
+        },
+    };
+
+    let mut rhs = x.clone();
+    rhs.square();
+    rhs.mul_assign(&x);
+    rhs.add_assign(curve.b());
+
+    let y = fp2_sqrt(&rhs).ok_or_else(|| {
+        ApiError::InputError("Invalid compressed G2 point: X is not a valid curve abscissa".to_owned())
+    })?;
+    let y = select_sign_fp2(y, y_is_odd);
+
+    Ok((CurveTwistPoint::point_from_xy(curve, x, y), rest))
+}
+
+/// Square root in `Fp2 = Fp[u]/(u^2 - non_residue)`, via the standard
+/// "complex method": for `a = a0 + a1*u`, compute `alpha = sqrt(a0^2 -
+/// non_residue*a1^2)` in `Fp`, then recover the two `Fp` halves of the
+/// result from `alpha` and `a0`.
+fn fp2_sqrt<'a, FE: ElementRepr>(value: &Fp2<'a, FE>) -> Option<Fp2<'a, FE>> {
+    if value.is_zero() {
+        return Some(value.clone());
+    }
+
+    // The non-residue is parsed from untrusted input per curve, so it has to
+    // come from this value's own `Extension2` rather than some inherent
+    // constant on `Fp`.
+    let extension = value.field();
+
+    let mut norm = value.c0.clone();
+    norm.square();
+    let mut c1_squared = value.c1.clone();
+    c1_squared.square();
+    c1_squared.mul_assign(&extension.non_residue);
+    norm.sub_assign(&c1_squared);
+
+    let alpha = fp_sqrt(&norm)?;
+
+    let mut delta = alpha.clone();
+    delta.add_assign(&value.c0);
+    fp_mul_by_inv_two(&mut delta);
+
+    let delta = match fp_sqrt(&delta) {
+        Some(root) => root,
+        None => {
+            let mut fallback = alpha.clone();
+            fallback.negate();
+            fallback.add_assign(&value.c0);
+            fp_mul_by_inv_two(&mut fallback);
+            fp_sqrt(&fallback)?
+        },
+    };
+
+    // c1 = value.c1 / (2 * delta)
+    let mut delta_doubled = delta.clone();
+    delta_doubled.double();
+    let delta_doubled_inv = delta_doubled.inverse()?;
+    let mut c1 = value.c1.clone();
+    c1.mul_assign(&delta_doubled_inv);
+
+    let mut result = Fp2::zero(extension);
+    result.c0 = delta;
+    result.c1 = c1;
+
+    let mut check = result.clone();
+    check.square();
+    if &check == value {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// Multiplies `value` in place by the inverse of `2` in `Fp`. There is no
+/// dedicated "divide by two" method on the field element types, so this
+/// computes `2^-1` explicitly via `Fp::inverse` instead of assuming one.
+fn fp_mul_by_inv_two<'a, FE: ElementRepr>(value: &mut Fp<'a, FE>) {
+    let field = value.field();
+    let mut two = Fp::one(field);
+    two.double();
+    let inv_two = two.inverse().expect("2 is invertible in an odd-characteristic prime field");
+    value.mul_assign(&inv_two);
+}
+
+/// Compares the `c1` limb first, falling back to `c0` if `c1` is zero --
+/// the Fp2 analogue of the single-limb parity rule used for G1.
+fn select_sign_fp2<'a, FE: ElementRepr>(root: Fp2<'a, FE>, want_odd: bool) -> Fp2<'a, FE> {
+    let is_odd = if !root.c1.is_zero() {
+        root.c1.into_repr().as_ref()[0] & 1 == 1
+    } else {
+        root.c0.into_repr().as_ref()[0] & 1 == 1
+    };
+
+    if !should_negate_root(is_odd, want_odd) {
+        root
+    } else {
+        let mut negated = root;
+        negated.negate();
+        negated
+    }
+}
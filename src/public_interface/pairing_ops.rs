@@ -12,8 +12,10 @@
 ///
 /// Assumptions:
 /// - one byte for length encoding
-/// 
-/// 
+///
+/// Points may be given either in uncompressed (x, y) form or compressed form
+/// (x plus a sign bit for y), selected by a one byte flag placed right after
+/// the twist type in the BLS12 pairing header. See DOC 8 for the encoding.
 
 use crate::weierstrass::curve;
 use crate::weierstrass::twist;
@@ -21,13 +23,16 @@ use crate::weierstrass::Group;
 use crate::fp::Fp;
 use crate::pairings::*;
 use crate::pairings::bls12::{Bls12Instance, TwistType};
+use crate::pairings::bn::BnInstance;
 use crate::extension_towers::fp2::{Fp2, Extension2};
 use crate::extension_towers::fp6_as_3_over_2::{Fp6, Extension3Over2};
 use crate::extension_towers::fp12_as_2_over3_over_2::{Fp12, Extension2Over3Over2};
 use crate::representation::ElementRepr;
 use crate::traits::FieldElement;
 use crate::field::biguint_to_u64_vec;
+use crate::field::PrimeField;
 use crate::sliding_window_exp::WindowExpBase;
+use num_bigint::BigUint;
 
 use super::decode_g1::*;
 use super::decode_utils::*;
@@ -68,6 +73,64 @@ struct PairingApiImplementation<FE: ElementRepr, GE: ElementRepr> {
     _marker_ge: std::marker::PhantomData<GE>
 }
 
+/// Selects what `pair_bls12` returns: the usual boolean verdict, the fully
+/// serialized `Fp12` pairing result, or the `Fp12` Miller-loop accumulator
+/// taken before the final exponentiation. `pair_bn` reads no output mode and
+/// always returns the boolean verdict.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PairingOutputMode {
+    Boolean,
+    Fp12,
+    MillerLoopFp12,
+}
+
+/// Serializes an `Fp12` element as its 12 `Fp` components, `modulus_len`
+/// bytes each, walking the tower low-component-first at every level
+/// (`c0` before `c1` for `Fp12`/`Fp2`, `c0`/`c1`/`c2` in order for `Fp6`) --
+/// the same order `decode_fp`/`decode_fp2` build components in.
+fn serialize_fp12<FE: ElementRepr>(modulus_len: usize, element: &Fp12<FE>) -> Vec<u8> {
+    let mut result = Vec::with_capacity(12 * modulus_len);
+
+    for c6 in [&element.c0, &element.c1].iter() {
+        for c2 in [&c6.c0, &c6.c1, &c6.c2].iter() {
+            result.extend(serialize_fp(modulus_len, &c2.c0));
+            result.extend(serialize_fp(modulus_len, &c2.c1));
+        }
+    }
+
+    result
+}
+
+/// Parses the one byte pairing output mode flag. Split out from `pair_bls12`
+/// so the flag/mode mapping can be exercised without needing a full pairing
+/// header to test against.
+fn parse_pairing_output_mode(flag: u8) -> Result<PairingOutputMode, ApiError> {
+    match flag {
+        PAIRING_OUTPUT_BOOLEAN => Ok(PairingOutputMode::Boolean),
+        PAIRING_OUTPUT_FP12 => Ok(PairingOutputMode::Fp12),
+        PAIRING_OUTPUT_MILLER_LOOP_FP12 => Ok(PairingOutputMode::MillerLoopFp12),
+        _ => Err(ApiError::UnknownParameter("Unknown pairing output mode supplied".to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod pairing_output_mode_tests {
+    use super::{parse_pairing_output_mode, PairingOutputMode};
+    use super::constants::*;
+
+    #[test]
+    fn maps_known_flags_to_their_mode() {
+        assert!(parse_pairing_output_mode(PAIRING_OUTPUT_BOOLEAN).unwrap() == PairingOutputMode::Boolean);
+        assert!(parse_pairing_output_mode(PAIRING_OUTPUT_FP12).unwrap() == PairingOutputMode::Fp12);
+        assert!(parse_pairing_output_mode(PAIRING_OUTPUT_MILLER_LOOP_FP12).unwrap() == PairingOutputMode::MillerLoopFp12);
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert!(parse_pairing_output_mode(0xff).is_err());
+    }
+}
+
 impl<FE: ElementRepr, GE: ElementRepr> PairingApi for PairingApiImplementation<FE, GE> {
     fn pair(bytes: &[u8]) -> Result<Vec<u8>, ApiError> {
         if bytes.len() < CURVE_TYPE_LENGTH {
@@ -79,6 +142,9 @@ impl<FE: ElementRepr, GE: ElementRepr> PairingApi for PairingApiImplementation<F
             BLS12 => {
                 PairingApiImplementation::<FE, GE>::pair_bls12(&rest)
             },
+            BN => {
+                PairingApiImplementation::<FE, GE>::pair_bn(&rest)
+            },
             _ => {
                 unimplemented!("Not implemented for not BLS12 curves");
             }
@@ -130,12 +196,33 @@ impl<FE: ElementRepr, GE: ElementRepr>PairingApiImplementation<FE, GE> {
 
         let twist_type = match twist_type_encoding[0] {
             TWIST_TYPE_D => TwistType::D,
-            TWIST_TYPE_M => TwistType::M, 
+            TWIST_TYPE_M => TwistType::M,
             _ => {
                 return Err(ApiError::UnknownParameter("Unknown twist type supplied".to_owned()));
             },
         };
 
+        if rest.len() < POINT_ENCODING_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get point encoding flag".to_owned()));
+        }
+
+        let (point_encoding, rest) = rest.split_at(POINT_ENCODING_LENGTH);
+
+        let is_compressed = match point_encoding[0] {
+            UNCOMPRESSED_POINT_ENCODING => false,
+            COMPRESSED_POINT_ENCODING => true,
+            _ => {
+                return Err(ApiError::UnknownParameter("Unknown point encoding supplied".to_owned()));
+            },
+        };
+
+        if rest.len() < PAIRING_OUTPUT_MODE_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get pairing output mode".to_owned()));
+        }
+
+        let (output_mode_encoding, rest) = rest.split_at(PAIRING_OUTPUT_MODE_LENGTH);
+        let output_mode = parse_pairing_output_mode(output_mode_encoding[0])?;
+
         let f_c1 = [Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2),
                     Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2)];
 
@@ -217,8 +304,16 @@ impl<FE: ElementRepr, GE: ElementRepr>PairingApiImplementation<FE, GE> {
         let mut g2_points = vec![];
 
         for _ in 0..num_pairs {
-            let (g1, rest) = decode_g1_point_from_xy(&global_rest, modulus_len, &g1_curve)?;
-            let (g2, rest) = decode_g2_point_from_xy_in_fp2(&rest, modulus_len, &g2_curve)?;
+            let (g1, rest) = if is_compressed {
+                decode_g1_point_from_x(&global_rest, modulus_len, &g1_curve)?
+            } else {
+                decode_g1_point_from_xy(&global_rest, modulus_len, &g1_curve)?
+            };
+            let (g2, rest) = if is_compressed {
+                decode_g2_point_from_x_in_fp2(&rest, modulus_len, &g2_curve)?
+            } else {
+                decode_g2_point_from_xy_in_fp2(&rest, modulus_len, &g2_curve)?
+            };
 
             global_rest = rest;
             if !g1.check_on_curve() || !g2.check_on_curve() {
@@ -245,7 +340,191 @@ impl<FE: ElementRepr, GE: ElementRepr>PairingApiImplementation<FE, GE> {
             fp12_extension: &extension_12,
         };
 
-        let pairing_result = engine.pair(&g1_points, &g2_points);
+        let miller_loop_only = output_mode == PairingOutputMode::MillerLoopFp12;
+        let pairing_result = engine.pair(&g1_points, &g2_points, miller_loop_only);
+
+        if pairing_result.is_none() {
+            return Err(ApiError::UnknownParameter("Pairing engine returned no value".to_owned()));
+        }
+
+        let pairing_result = pairing_result.unwrap();
+        let result = match output_mode {
+            PairingOutputMode::Boolean => {
+                let one_fp12 = Fp12::one(&extension_12);
+                if pairing_result == one_fp12 {
+                    vec![1u8]
+                } else {
+                    vec![0u8]
+                }
+            },
+            PairingOutputMode::Fp12 | PairingOutputMode::MillerLoopFp12 => {
+                serialize_fp12(modulus_len, &pairing_result)
+            },
+        };
+
+        Ok(result)
+    }
+
+    fn pair_bn(bytes: &[u8]) -> Result<Vec<u8>, ApiError> {
+        let (base_field, modulus_len, modulus, rest) = parse_base_field_from_encoding::<FE>(&bytes)?;
+        let (a_fp, b_fp, rest) = parse_ab_in_base_field_from_encoding(&rest, modulus_len, &base_field)?;
+        if !a_fp.is_zero() {
+            return Err(ApiError::UnknownParameter("A parameter must be zero for BN curve".to_owned()));
+        }
+        let (group, _order_len, order, rest) = parse_group_order_from_encoding::<GE>(rest)?;
+        let g1_curve = curve::WeierstrassCurve::new(&group, a_fp, b_fp.clone());
+
+        // Same tower-building sequence as BLS12:
+        // - non-residue for Fp2
+        // - non-residue for Fp6
+        // - twist type M/D
+        // - parameter U
+        // - sign of U
+        // - number of pairs
+        // - list of encoded pairs
+
+        let (fp_non_residue, rest) = decode_fp(&rest, modulus_len, &base_field)?;
+        let mut extension_2 = Extension2 {
+            field: &base_field,
+            non_residue: fp_non_residue,
+            frobenius_coeffs_c1: [Fp::zero(&base_field), Fp::zero(&base_field)]
+        };
+
+        let coeffs = frobenius_calculator_fp2(&extension_2).map_err(|_| {
+            ApiError::InputError("Failed to calculate Frobenius coeffs for Fp2".to_owned())
+        })?;
+        extension_2.frobenius_coeffs_c1 = coeffs;
+
+        let (fp2_non_residue, rest) = decode_fp2(&rest, modulus_len, &extension_2)?;
+
+        if rest.len() < TWIST_TYPE_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get twist type".to_owned()));
+        }
+
+        let (twist_type_encoding, rest) = rest.split_at(TWIST_TYPE_LENGTH);
+
+        let twist_type = match twist_type_encoding[0] {
+            TWIST_TYPE_D => TwistType::D,
+            TWIST_TYPE_M => TwistType::M,
+            _ => {
+                return Err(ApiError::UnknownParameter("Unknown twist type supplied".to_owned()));
+            },
+        };
+
+        let f_c1 = [Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2),
+                    Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2)];
+
+        let mut extension_6 = Extension3Over2 {
+            non_residue: fp2_non_residue.clone(),
+            field: &extension_2,
+            frobenius_coeffs_c1: f_c1.clone(),
+            frobenius_coeffs_c2: f_c1,
+        };
+
+        let exp_base = WindowExpBase::new(&fp2_non_residue, Fp2::one(&extension_2), 8, 7);
+
+        let (coeffs_c1, coeffs_c2) = frobenius_calculator_fp6_as_3_over_2_using_sliding_window(modulus.clone(), &exp_base, &extension_6).map_err(|_| {
+            ApiError::UnknownParameter("Can not calculate Frobenius coefficients for Fp6".to_owned())
+        })?;
+
+        extension_6.frobenius_coeffs_c1 = coeffs_c1;
+        extension_6.frobenius_coeffs_c2 = coeffs_c2;
+
+        let f_c1 = [Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2),
+                    Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2),
+                    Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2),
+                    Fp2::zero(&extension_2), Fp2::zero(&extension_2), Fp2::zero(&extension_2)];
+
+        let mut extension_12 = Extension2Over3Over2 {
+            non_residue: Fp6::zero(&extension_6),
+            field: &extension_6,
+            frobenius_coeffs_c1: f_c1,
+        };
+
+        let coeffs = frobenius_calculator_fp12_using_sliding_window(modulus, &exp_base, &extension_12).map_err(|_| {
+            ApiError::InputError("Can not calculate Frobenius coefficients for Fp12".to_owned())
+        })?;
+        extension_12.frobenius_coeffs_c1 = coeffs;
+
+        let fp2_non_residue_inv = fp2_non_residue.inverse().ok_or(ApiError::UnexpectedZero("Fp2 non-residue must be invertible".to_owned()))?;
+
+        let b_fp2 = match twist_type {
+            TwistType::D => {
+                let mut b_fp2 = fp2_non_residue_inv.clone();
+                b_fp2.mul_by_fp(&b_fp);
+
+                b_fp2
+            },
+            TwistType::M => {
+                let mut b_fp2 = fp2_non_residue.clone();
+                b_fp2.mul_by_fp(&b_fp);
+
+                b_fp2
+            },
+        };
+
+        let a_fp2 = Fp2::zero(&extension_2);
+        let g2_curve = twist::WeierstrassCurveTwist::new(&group, &extension_2, a_fp2, b_fp2);
+
+        let (u, rest) = decode_biguint_with_length(&rest)?;
+        if rest.len() < SIGN_ENCODING_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get U sign encoding".to_owned()));
+        }
+        let (u_sign, rest) = rest.split_at(SIGN_ENCODING_LENGTH);
+        let u_is_negative = match u_sign[0] {
+            SIGN_PLUS => false,
+            SIGN_MINUS => true,
+            _ => {
+                return Err(ApiError::InputError("U sign is not encoded properly".to_owned()));
+            },
+        };
+
+        if rest.len() < BYTES_FOR_LENGTH_ENCODING {
+            return Err(ApiError::InputError("Input is not long enough to get number of pairs".to_owned()));
+        }
+
+        let (num_pairs_encoding, rest) = rest.split_at(BYTES_FOR_LENGTH_ENCODING);
+        let num_pairs = num_pairs_encoding[0] as usize;
+
+        let mut global_rest = rest;
+
+        let mut g1_points = vec![];
+        let mut g2_points = vec![];
+
+        for _ in 0..num_pairs {
+            let (g1, rest) = decode_g1_point_from_xy(&global_rest, modulus_len, &g1_curve)?;
+            let (g2, rest) = decode_g2_point_from_xy_in_fp2(&rest, modulus_len, &g2_curve)?;
+
+            global_rest = rest;
+            if !g1.check_on_curve() || !g2.check_on_curve() {
+                return Err(ApiError::InputError("G1 or G2 point is not on curve".to_owned()));
+            }
+
+            if !g1.check_correct_subgroup() || !g2.check_correct_subgroup() {
+                return Err(ApiError::InputError("G1 or G2 point is not in the expected subgroup".to_owned()));
+            }
+
+            g1_points.push(g1);
+            g2_points.push(g2);
+        }
+
+        // BnInstance drives the Ate Miller loop over |6u+2| instead of x, and adds the
+        // two trailing Frobenius addition steps (at pi(Q) and -pi^2(Q)) before a
+        // final exponentiation with the same easy part as BLS12 but a BN-specific
+        // hard part.
+        let engine = BnInstance {
+            u: biguint_to_u64_vec(u),
+            u_is_negative: u_is_negative,
+            twist_type: twist_type,
+            base_field: &base_field,
+            curve: &g1_curve,
+            curve_twist: &g2_curve,
+            fp2_extension: &extension_2,
+            fp6_extension: &extension_6,
+            fp12_extension: &extension_12,
+        };
+
+        let pairing_result = engine.pair(&g1_points, &g2_points, false);
 
         if pairing_result.is_none() {
             return Err(ApiError::UnknownParameter("Pairing engine returned no value".to_owned()));
@@ -261,4 +540,344 @@ impl<FE: ElementRepr, GE: ElementRepr>PairingApiImplementation<FE, GE> {
 
         Ok(result)
     }
+}
+
+pub struct PublicMsmApi;
+
+impl MsmApi for PublicMsmApi {
+    fn msm(bytes: &[u8]) -> Result<Vec<u8>, ApiError> {
+        use crate::field::*;
+        if bytes.len() < CURVE_TYPE_LENGTH {
+            return Err(ApiError::InputError("Input should be longer than curve type encoding".to_owned()));
+        }
+        let (_curve_type, rest) = bytes.split_at(CURVE_TYPE_LENGTH);
+        let (modulus, _, _, _, order, _, _) = parse_encodings(&rest)?;
+        let modulus_limbs = (modulus.bits() / 64) + 1;
+        let order_limbs = (order.bits() / 64) + 1;
+
+        let result: Result<Vec<u8>, ApiError> = expand_for_modulus_and_group_limbs!(modulus_limbs, order_limbs, MsmApiImplementation, bytes, msm);
+
+        result
+    }
+}
+
+pub trait MsmApi {
+    fn msm(bytes: &[u8]) -> Result<Vec<u8>, ApiError>;
+}
+
+struct MsmApiImplementation<FE: ElementRepr, GE: ElementRepr> {
+    _marker_fe: std::marker::PhantomData<FE>,
+    _marker_ge: std::marker::PhantomData<GE>
+}
+
+impl<FE: ElementRepr, GE: ElementRepr> MsmApi for MsmApiImplementation<FE, GE> {
+    fn msm(bytes: &[u8]) -> Result<Vec<u8>, ApiError> {
+        let (base_field, modulus_len, _modulus, rest) = parse_base_field_from_encoding::<FE>(&bytes)?;
+        let (a_fp, b_fp, rest) = parse_ab_in_base_field_from_encoding(&rest, modulus_len, &base_field)?;
+        let (group, order_len, order, rest) = parse_group_order_from_encoding::<GE>(rest)?;
+
+        if rest.len() < MSM_POINT_TYPE_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get MSM point type".to_owned()));
+        }
+
+        let (point_type_encoding, rest) = rest.split_at(MSM_POINT_TYPE_LENGTH);
+
+        match point_type_encoding[0] {
+            MSM_POINT_TYPE_G1 => {
+                Self::msm_g1(base_field, modulus_len, a_fp, b_fp, group, order_len, order, rest)
+            },
+            MSM_POINT_TYPE_G2 => {
+                Self::msm_g2(base_field, modulus_len, a_fp, b_fp, group, order_len, order, rest)
+            },
+            _ => {
+                Err(ApiError::UnknownParameter("Unknown MSM point type supplied".to_owned()))
+            },
+        }
+    }
+}
+
+impl<FE: ElementRepr, GE: ElementRepr> MsmApiImplementation<FE, GE> {
+    fn msm_g1(
+        _base_field: PrimeField<FE>,
+        modulus_len: usize,
+        a_fp: Fp<FE>,
+        b_fp: Fp<FE>,
+        group: PrimeField<GE>,
+        order_len: usize,
+        order: BigUint,
+        rest: &[u8],
+    ) -> Result<Vec<u8>, ApiError> {
+        let curve = curve::WeierstrassCurve::new(&group, a_fp, b_fp);
+
+        if rest.len() < BYTES_FOR_LENGTH_ENCODING {
+            return Err(ApiError::InputError("Input is not long enough to get number of pairs".to_owned()));
+        }
+
+        let (num_pairs_encoding, rest) = rest.split_at(BYTES_FOR_LENGTH_ENCODING);
+        let num_pairs = num_pairs_encoding[0] as usize;
+
+        let mut global_rest = rest;
+
+        let mut bases = Vec::with_capacity(num_pairs);
+        let mut scalars = Vec::with_capacity(num_pairs);
+
+        for _ in 0..num_pairs {
+            let (point, rest) = decode_g1_point_from_xy(&global_rest, modulus_len, &curve)?;
+
+            if !point.check_on_curve() {
+                return Err(ApiError::InputError("Point is not on curve".to_owned()));
+            }
+
+            if !point.check_correct_subgroup() {
+                return Err(ApiError::InputError("Point is not in the expected subgroup".to_owned()));
+            }
+
+            let (scalar, rest) = decode_scalar_with_length(&rest, order_len)?;
+
+            bases.push(point);
+            scalars.push(scalar);
+            global_rest = rest;
+        }
+
+        let result = pippenger_msm(&bases, &scalars, order.bits() as usize)
+            .ok_or(ApiError::UnexpectedZero("Multi-scalar multiplication over an empty point list".to_owned()))?;
+
+        serialize_g1_point(modulus_len, &result)
+    }
+
+    fn msm_g2(
+        base_field: PrimeField<FE>,
+        modulus_len: usize,
+        a_fp: Fp<FE>,
+        b_fp: Fp<FE>,
+        group: PrimeField<GE>,
+        order_len: usize,
+        order: BigUint,
+        rest: &[u8],
+    ) -> Result<Vec<u8>, ApiError> {
+        if !a_fp.is_zero() {
+            return Err(ApiError::UnknownParameter("A parameter must be zero for G2 MSM over these curves".to_owned()));
+        }
+
+        // Same Fp2 tower and twisted-B construction as the G2 half of
+        // `pair_bls12` -- MSM over G2 needs an Fp2 curve, but none of the
+        // Fp6/Fp12 pairing tower above it.
+        let (fp_non_residue, rest) = decode_fp(rest, modulus_len, &base_field)?;
+        let mut extension_2 = Extension2 {
+            field: &base_field,
+            non_residue: fp_non_residue,
+            frobenius_coeffs_c1: [Fp::zero(&base_field), Fp::zero(&base_field)]
+        };
+
+        let coeffs = frobenius_calculator_fp2(&extension_2).map_err(|_| {
+            ApiError::InputError("Failed to calculate Frobenius coeffs for Fp2".to_owned())
+        })?;
+        extension_2.frobenius_coeffs_c1 = coeffs;
+
+        let (fp2_non_residue, rest) = decode_fp2(rest, modulus_len, &extension_2)?;
+
+        if rest.len() < TWIST_TYPE_LENGTH {
+            return Err(ApiError::InputError("Input is not long enough to get twist type".to_owned()));
+        }
+
+        let (twist_type_encoding, rest) = rest.split_at(TWIST_TYPE_LENGTH);
+
+        let twist_type = match twist_type_encoding[0] {
+            TWIST_TYPE_D => TwistType::D,
+            TWIST_TYPE_M => TwistType::M,
+            _ => {
+                return Err(ApiError::UnknownParameter("Unknown twist type supplied".to_owned()));
+            },
+        };
+
+        let fp2_non_residue_inv = fp2_non_residue.inverse().ok_or(ApiError::UnexpectedZero("Fp2 non-residue must be invertible".to_owned()))?;
+
+        let b_fp2 = match twist_type {
+            TwistType::D => {
+                let mut b_fp2 = fp2_non_residue_inv.clone();
+                b_fp2.mul_by_fp(&b_fp);
+
+                b_fp2
+            },
+            TwistType::M => {
+                let mut b_fp2 = fp2_non_residue.clone();
+                b_fp2.mul_by_fp(&b_fp);
+
+                b_fp2
+            },
+        };
+
+        let a_fp2 = Fp2::zero(&extension_2);
+        let curve = twist::WeierstrassCurveTwist::new(&group, &extension_2, a_fp2, b_fp2);
+
+        if rest.len() < BYTES_FOR_LENGTH_ENCODING {
+            return Err(ApiError::InputError("Input is not long enough to get number of pairs".to_owned()));
+        }
+
+        let (num_pairs_encoding, rest) = rest.split_at(BYTES_FOR_LENGTH_ENCODING);
+        let num_pairs = num_pairs_encoding[0] as usize;
+
+        let mut global_rest = rest;
+
+        let mut bases = Vec::with_capacity(num_pairs);
+        let mut scalars = Vec::with_capacity(num_pairs);
+
+        for _ in 0..num_pairs {
+            let (point, rest) = decode_g2_point_from_xy_in_fp2(&global_rest, modulus_len, &curve)?;
+
+            if !point.check_on_curve() {
+                return Err(ApiError::InputError("Point is not on curve".to_owned()));
+            }
+
+            if !point.check_correct_subgroup() {
+                return Err(ApiError::InputError("Point is not in the expected subgroup".to_owned()));
+            }
+
+            let (scalar, rest) = decode_scalar_with_length(&rest, order_len)?;
+
+            bases.push(point);
+            scalars.push(scalar);
+            global_rest = rest;
+        }
+
+        let result = pippenger_msm(&bases, &scalars, order.bits() as usize)
+            .ok_or(ApiError::UnexpectedZero("Multi-scalar multiplication over an empty point list".to_owned()))?;
+
+        serialize_g2_point_in_fp2(modulus_len, &result)
+    }
+}
+
+/// Multi-scalar multiplication `sum_i scalars[i] * bases[i]` using the bucket
+/// (Pippenger) method, following the same shape as bellman's `multiexp`:
+/// each scalar is split into `ceil(num_bits / c)` windows of width `c`, every
+/// window buckets its points by digit value, each bucket set is collapsed
+/// with the running-sum trick in one pass, and windows are combined from the
+/// most significant down, doubling the accumulator `c` times in between.
+fn pippenger_msm<G: Group + Clone>(bases: &[G], scalars: &[Vec<u64>], num_bits: usize) -> Option<G> {
+    if bases.is_empty() {
+        return None;
+    }
+
+    let c = msm_window_size(bases.len());
+    let num_windows = (num_bits + c - 1) / c;
+
+    let mut acc: Option<G> = None;
+
+    for window_idx in (0..num_windows).rev() {
+        if let Some(acc) = acc.as_mut() {
+            for _ in 0..c {
+                acc.double();
+            }
+        }
+
+        let mut buckets: Vec<Option<G>> = vec![None; (1usize << c) - 1];
+
+        for (base, scalar) in bases.iter().zip(scalars.iter()) {
+            let digit = msm_window_digit(scalar, window_idx, c);
+            if digit != 0 {
+                match buckets[(digit - 1) as usize].as_mut() {
+                    Some(bucket) => bucket.add_assign(base),
+                    None => buckets[(digit - 1) as usize] = Some(base.clone()),
+                }
+            }
+        }
+
+        // Running-sum reduction: one pass from the top bucket down accumulates
+        // sum_j j*bucket_j in about 2*(2^c) additions.
+        let mut running_sum: Option<G> = None;
+        let mut window_sum: Option<G> = None;
+
+        for bucket in buckets.into_iter().rev() {
+            if let Some(bucket) = bucket {
+                match running_sum.as_mut() {
+                    Some(running_sum) => running_sum.add_assign(&bucket),
+                    None => running_sum = Some(bucket),
+                }
+            }
+
+            if let Some(running_sum) = running_sum.as_ref() {
+                match window_sum.as_mut() {
+                    Some(window_sum) => window_sum.add_assign(running_sum),
+                    None => window_sum = Some(running_sum.clone()),
+                }
+            }
+        }
+
+        if let Some(window_sum) = window_sum {
+            match acc.as_mut() {
+                Some(acc) => acc.add_assign(&window_sum),
+                None => acc = Some(window_sum),
+            }
+        }
+    }
+
+    acc
+}
+
+/// Bucket count grows with the number of terms (bellman's multiexp heuristic):
+/// a wider window only pays for itself once there are enough points to fill
+/// the extra buckets it creates.
+fn msm_window_size(num_terms: usize) -> usize {
+    if num_terms < 32 {
+        3
+    } else {
+        ((num_terms as f64).ln() * 0.7) as usize + 2
+    }
+}
+
+fn msm_window_digit(scalar: &[u64], window_idx: usize, c: usize) -> u64 {
+    let bit_start = window_idx * c;
+    let limb = bit_start / 64;
+    let bit_in_limb = bit_start % 64;
+
+    if limb >= scalar.len() {
+        return 0;
+    }
+
+    let mut digit = scalar[limb] >> bit_in_limb;
+    if bit_in_limb + c > 64 && limb + 1 < scalar.len() {
+        digit |= scalar[limb + 1] << (64 - bit_in_limb);
+    }
+
+    digit & ((1u64 << c) - 1)
+}
+
+#[cfg(test)]
+mod msm_tests {
+    use super::{msm_window_size, msm_window_digit};
+
+    #[test]
+    fn small_term_counts_use_the_minimum_window() {
+        assert_eq!(msm_window_size(1), 3);
+        assert_eq!(msm_window_size(31), 3);
+    }
+
+    #[test]
+    fn window_grows_with_term_count() {
+        let small = msm_window_size(32);
+        let large = msm_window_size(1_000_000);
+        assert!(large > small);
+    }
+
+    #[test]
+    fn extracts_digits_within_a_single_limb() {
+        // scalar = 0b1011 (c=2 windows: digit0 = 0b11, digit1 = 0b10)
+        let scalar = [0b1011u64];
+        assert_eq!(msm_window_digit(&scalar, 0, 2), 0b11);
+        assert_eq!(msm_window_digit(&scalar, 1, 2), 0b10);
+    }
+
+    #[test]
+    fn extracts_digits_spanning_a_limb_boundary() {
+        // A 5-bit window starting at bit 60 straddles limb 0 and limb 1.
+        let scalar = [0xFFFF_FFFF_FFFF_FFFFu64, 0b101u64];
+        assert_eq!(msm_window_digit(&scalar, 12, 5), 0b11111);
+    }
+
+    #[test]
+    fn returns_zero_past_the_end_of_the_scalar() {
+        let scalar = [1u64];
+        // c=4, window 16 starts at bit 64, past the scalar's only limb.
+        assert_eq!(msm_window_digit(&scalar, 16, 4), 0);
+    }
 }
\ No newline at end of file